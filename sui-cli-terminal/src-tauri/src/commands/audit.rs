@@ -0,0 +1,144 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager};
+use tauri::State as TauriState;
+
+use crate::{AppState, CommandOutput};
+
+const AUDIT_LOG_FILENAME: &str = "audit-log.ndjson";
+const MAX_AUDIT_LOG_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_RECORDS_AFTER_ROTATE: usize = 2_000;
+
+/// One line of the on-disk audit log. `args` is sanitized the same way
+/// command output is, so private keys/mnemonics never hit disk.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AuditRecord {
+    timestamp_ms: u64,
+    program: String,
+    args: Vec<String>,
+    exit_code: i32,
+    duration_ms: u64,
+    address: Option<String>,
+    environment: Option<String>,
+}
+
+fn audit_log_path(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app data dir: {}", e))?;
+    Ok(dir.join(AUDIT_LOG_FILENAME))
+}
+
+/// Appends a sanitized record of one `sui`/`walrus` invocation to the
+/// rotating on-disk audit log. Called after every command completes;
+/// callers discard the error, so a failure here never fails the command
+/// itself.
+pub fn record(
+    app: &AppHandle,
+    state: &TauriState<'_, AppState>,
+    program: &str,
+    args: &[String],
+    output: &CommandOutput,
+) -> Result<(), String> {
+    let path = audit_log_path(app)?;
+
+    let sanitized_args: Vec<String> = args
+        .iter()
+        .map(|a| crate::utils::sanitizer::sanitize_output(a))
+        .collect();
+
+    let entry = AuditRecord {
+        timestamp_ms: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        program: program.to_string(),
+        args: sanitized_args,
+        exit_code: output.exit_code,
+        duration_ms: output.duration_ms,
+        address: state.current_address.lock().unwrap().clone(),
+        environment: state.current_environment.lock().unwrap().clone(),
+    };
+
+    let line = serde_json::to_string(&entry).map_err(|e| e.to_string())?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|e| e.to_string())?;
+    writeln!(file, "{}", line).map_err(|e| e.to_string())?;
+    drop(file);
+
+    rotate_if_needed(&path)
+}
+
+/// Keeps the log from growing unbounded: once it exceeds `MAX_AUDIT_LOG_BYTES`
+/// it's truncated down to the most recent `MAX_RECORDS_AFTER_ROTATE` lines.
+fn rotate_if_needed(path: &PathBuf) -> Result<(), String> {
+    let metadata = std::fs::metadata(path).map_err(|e| e.to_string())?;
+    if metadata.len() <= MAX_AUDIT_LOG_BYTES {
+        return Ok(());
+    }
+
+    let contents = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let lines: Vec<&str> = contents.lines().collect();
+    let kept = if lines.len() > MAX_RECORDS_AFTER_ROTATE {
+        &lines[lines.len() - MAX_RECORDS_AFTER_ROTATE..]
+    } else {
+        &lines[..]
+    };
+
+    std::fs::write(path, format!("{}\n", kept.join("\n"))).map_err(|e| e.to_string())
+}
+
+/// Returns audit records, most recent first, optionally capped at `limit` and
+/// filtered to entries whose program or args contain `filter`.
+#[command]
+pub fn get_audit_log(
+    limit: Option<usize>,
+    filter: Option<String>,
+    app: AppHandle,
+) -> Result<Vec<AuditRecord>, String> {
+    let path = audit_log_path(&app)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contents = std::fs::read_to_string(&path).map_err(|e| e.to_string())?;
+
+    let mut records: Vec<AuditRecord> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str::<AuditRecord>(line).ok())
+        .filter(|r| match &filter {
+            Some(needle) => {
+                r.program.contains(needle.as_str())
+                    || r.args.iter().any(|a| a.contains(needle.as_str()))
+            }
+            None => true,
+        })
+        .collect();
+
+    records.reverse();
+
+    if let Some(limit) = limit {
+        records.truncate(limit);
+    }
+
+    Ok(records)
+}
+
+#[command]
+pub fn clear_audit_log(app: AppHandle) -> Result<(), String> {
+    let path = audit_log_path(&app)?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
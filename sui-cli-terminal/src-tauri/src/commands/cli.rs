@@ -1,34 +1,32 @@
 use tauri::command;
 use std::process::Command as StdCommand;
 use std::time::Instant;
-use crate::{CommandOutput, AppState};
+use serde::Serialize;
+use crate::{CommandOutput, AppState, IsOnline};
+use tauri::AppHandle;
+use tauri::Emitter;
 use tauri::State as TauriState;
 
-#[command]
-pub async fn execute_sui_command(
-    args: Vec<String>,
-    state: TauriState<'_, AppState>,
-) -> Result<CommandOutput, String> {
+#[derive(Clone, Serialize)]
+struct CacheHitEvent {
+    key: String,
+}
+
+/// Runs a single `sui` invocation and returns the sanitized output. Shared by
+/// `execute_sui_command` (retried for `client` subcommands) and anything else
+/// that just needs one attempt.
+async fn run_sui_once(args: &[String]) -> Result<CommandOutput, String> {
     let start = Instant::now();
 
     let output = StdCommand::new("sui")
-        .args(&args)
+        .args(args)
         .output()
         .map_err(|e| format!("Failed to execute: {}", e))?;
 
     let duration_ms = start.elapsed().as_millis() as u64;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-
-    // Sanitize sensitive data
-    let sanitized_stdout = crate::utils::sanitizer::sanitize_output(&stdout);
-    let sanitized_stderr = crate::utils::sanitizer::sanitize_output(&stderr);
-
-    // Update state
-    if let Ok(mut cmd) = state.last_command.lock() {
-        *cmd = args.join(" ");
-    }
+    let sanitized_stdout = crate::utils::sanitizer::sanitize_output(&String::from_utf8_lossy(&output.stdout));
+    let sanitized_stderr = crate::utils::sanitizer::sanitize_output(&String::from_utf8_lossy(&output.stderr));
 
     Ok(CommandOutput {
         stdout: sanitized_stdout,
@@ -38,21 +36,121 @@ pub async fn execute_sui_command(
     })
 }
 
+#[command]
+pub async fn execute_sui_command(
+    args: Vec<String>,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<CommandOutput, String> {
+    // `client` subcommands hit an RPC/full-node endpoint and are worth
+    // retrying on transient network failures; everything else (keytool, move,
+    // etc.) is local and should fail fast.
+    let hits_network = args.first().map(|s| s == "client").unwrap_or(false);
+    // `client objects`/`client gas` are idempotent reads, so they're worth
+    // memoizing; other `client` subcommands (publish, transfer, ...) mutate
+    // state and must always run.
+    let is_cacheable = matches!(
+        (args.first().map(String::as_str), args.get(1).map(String::as_str)),
+        (Some("client"), Some("objects")) | (Some("client"), Some("gas"))
+    );
+    let cache_key = crate::utils::cache::key("sui", &args);
+    let id = args.join(" ");
+
+    if is_cacheable {
+        if let Some(cached) = crate::utils::cache::get::<CommandOutput>(&state, &cache_key) {
+            let _ = app.emit("cache-hit", CacheHitEvent { key: cache_key });
+            return Ok(cached);
+        }
+    }
+
+    let result = if hits_network {
+        let online = *state.online_status.lock().unwrap();
+        crate::utils::retry::run_with_retry(&id, &app, &online, || run_sui_once(&args)).await?
+    } else {
+        run_sui_once(&args).await?
+    };
+
+    if is_cacheable && result.exit_code == 0 {
+        crate::utils::cache::put(&state, &cache_key, &result);
+    }
+
+    // Update state
+    if let Ok(mut cmd) = state.last_command.lock() {
+        *cmd = args.join(" ");
+    }
+
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &result);
+
+    Ok(result)
+}
+
+/// Streaming counterpart to `execute_sui_command`: emits a `command-output`
+/// event per sanitized line as the child produces it (instead of buffering the
+/// whole run), so a long `sui client publish` no longer freezes the UI. `id` is
+/// an opaque handle chosen by the frontend that ties these events together and
+/// lets `cancel_command` find the right child to kill.
+#[command]
+pub async fn execute_sui_command_streaming(
+    id: String,
+    args: Vec<String>,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<CommandOutput, String> {
+    let output = crate::utils::streaming::run_streaming("sui", &args, id, &app, &state).await?;
+
+    if let Ok(mut cmd) = state.last_command.lock() {
+        *cmd = args.join(" ");
+    }
+
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &output);
+
+    Ok(output)
+}
+
+/// Kills the child process tracked under `id` by a previous call to
+/// `execute_sui_command_streaming` or `upload_blob_streaming`, e.g. to let a
+/// user abort a hung `sui client publish`.
+#[command]
+pub fn cancel_command(id: String, state: TauriState<'_, AppState>) -> Result<(), String> {
+    crate::utils::streaming::cancel(&id, &state)
+}
+
 #[command]
 pub async fn list_keys(
-    _state: TauriState<'_, AppState>,
-) -> Result<Vec<serde_json::Value>, String> {
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<Vec<crate::utils::parser::KeyInfo>, String> {
+    let args = vec!["keytool".to_string(), "list".to_string(), "--json".to_string()];
+    let cache_key = crate::utils::cache::key("sui", &args);
+
+    if let Some(cached) = crate::utils::cache::get::<Vec<crate::utils::parser::KeyInfo>>(&state, &cache_key) {
+        let _ = app.emit("cache-hit", CacheHitEvent { key: cache_key });
+        return Ok(cached);
+    }
+
     let output = StdCommand::new("sui")
-        .args(&["keytool", "list"])
+        .args(&args)
         .output()
         .map_err(|e| format!("Failed to list keys: {}", e))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    
+
     // Parse output and return structured data
     let keys = crate::utils::parser::parse_keys(&stdout)
         .map_err(|e| format!("Parse error: {}", e))?;
 
+    let result = CommandOutput {
+        stdout: stdout.to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code: output.status.code().unwrap_or(-1),
+        duration_ms: 0,
+    };
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &result);
+
+    if result.exit_code == 0 {
+        crate::utils::cache::put(&state, &cache_key, &keys);
+    }
+
     Ok(keys)
 }
 
@@ -60,60 +158,193 @@ pub async fn list_keys(
 pub async fn generate_key(
     scheme: String,
     word_length: Option<u32>,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
 ) -> Result<CommandOutput, String> {
-    let mut cmd = StdCommand::new("sui");
-    cmd.arg("keytool").arg("generate").arg(&scheme);
-    
+    let mut args = vec!["keytool".to_string(), "generate".to_string(), scheme];
+
     if let Some(length) = word_length {
-        cmd.arg("--word-length").arg(length.to_string());
+        args.push("--word-length".to_string());
+        args.push(length.to_string());
     }
 
-    let output = cmd.output()
+    let output = StdCommand::new("sui")
+        .args(&args)
+        .output()
         .map_err(|e| format!("Failed to generate key: {}", e))?;
 
-    Ok(CommandOutput {
+    let result = CommandOutput {
         stdout: String::from_utf8_lossy(&output.stdout).to_string(),
         stderr: String::from_utf8_lossy(&output.stderr).to_string(),
         exit_code: output.status.code().unwrap_or(-1),
         duration_ms: 0,
-    })
+    };
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &result);
+    // Both the active-address-scoped `client` reads and the key list itself
+    // are now stale.
+    crate::utils::cache::invalidate_prefix(&state, "sui:client");
+    crate::utils::cache::invalidate_prefix(&state, "sui:keytool");
+
+    Ok(result)
 }
 
 #[command]
-pub async fn set_active_key(address: String) -> Result<CommandOutput, String> {
+pub async fn set_active_key(
+    address: String,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<CommandOutput, String> {
+    let args = vec![
+        "client".to_string(),
+        "switch".to_string(),
+        "--address".to_string(),
+        address.clone(),
+    ];
+
     let output = StdCommand::new("sui")
-        .args(&["client", "switch", "--address", &address])
+        .args(&args)
         .output()
         .map_err(|e| format!("Failed to switch key: {}", e))?;
 
-    Ok(CommandOutput {
+    let result = CommandOutput {
         stdout: String::from_utf8_lossy(&output.stdout).to_string(),
         stderr: String::from_utf8_lossy(&output.stderr).to_string(),
         exit_code: output.status.code().unwrap_or(-1),
         duration_ms: 0,
-    })
+    };
+
+    if result.exit_code == 0 {
+        *state.current_address.lock().unwrap() = Some(address);
+        crate::utils::cache::invalidate_prefix(&state, "sui:client");
+    }
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &result);
+
+    Ok(result)
 }
 
 #[command]
-pub async fn get_active_address() -> Result<String, String> {
+pub async fn get_active_address(
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<String, String> {
+    let args = vec!["client".to_string(), "active-address".to_string()];
+
     let output = StdCommand::new("sui")
-        .args(&["client", "active-address"])
+        .args(&args)
         .output()
         .map_err(|e| format!("Failed: {}", e))?;
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    let address = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    *state.current_address.lock().unwrap() = Some(address.clone());
+
+    let result = CommandOutput {
+        stdout: address.clone(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code: output.status.code().unwrap_or(-1),
+        duration_ms: 0,
+    };
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &result);
+
+    Ok(address)
 }
 
 #[command]
-pub async fn get_environment() -> Result<serde_json::Value, String> {
+pub async fn get_environment(
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<Vec<crate::utils::parser::EnvInfo>, String> {
+    let args = vec!["client".to_string(), "envs".to_string(), "--json".to_string()];
+    let cache_key = crate::utils::cache::key("sui", &args);
+
+    if let Some(cached) = crate::utils::cache::get::<Vec<crate::utils::parser::EnvInfo>>(&state, &cache_key) {
+        let _ = app.emit("cache-hit", CacheHitEvent { key: cache_key });
+        return Ok(cached);
+    }
+
     let output = StdCommand::new("sui")
-        .args(&["client", "envs"])
+        .args(&args)
         .output()
         .map_err(|e| format!("Failed: {}", e))?;
 
     let stdout = String::from_utf8_lossy(&output.stdout);
-    
-    Ok(serde_json::json!({
-        "envs": stdout,
-    }))
+    let envs = crate::utils::parser::parse_envs(&stdout)
+        .map_err(|e| format!("Parse error: {}", e))?;
+
+    if let Some(active) = envs.iter().find(|e| e.active) {
+        *state.current_environment.lock().unwrap() = Some(active.alias.clone());
+    }
+
+    let result = CommandOutput {
+        stdout: stdout.to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code: output.status.code().unwrap_or(-1),
+        duration_ms: 0,
+    };
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &result);
+
+    if result.exit_code == 0 {
+        crate::utils::cache::put(&state, &cache_key, &envs);
+    }
+
+    Ok(envs)
+}
+
+/// Probes the active environment's RPC endpoint and updates
+/// `AppState::online_status` with the result, so the frontend can gate
+/// actions and the retry loop can short-circuit when known-offline.
+#[command]
+pub async fn refresh_online_status(
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<IsOnline, String> {
+    let args = vec!["client".to_string(), "envs".to_string(), "--json".to_string()];
+    let output = StdCommand::new("sui")
+        .args(&args)
+        .output()
+        .map_err(|e| format!("Failed: {}", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let envs = crate::utils::parser::parse_envs(&stdout).unwrap_or_default();
+    let active = envs.iter().find(|e| e.active);
+
+    if let Some(env) = active {
+        *state.current_environment.lock().unwrap() = Some(env.alias.clone());
+    }
+
+    let result = CommandOutput {
+        stdout: stdout.to_string(),
+        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        exit_code: output.status.code().unwrap_or(-1),
+        duration_ms: 0,
+    };
+    let _ = crate::commands::audit::record(&app, &state, "sui", &args, &result);
+
+    let status = match active {
+        Some(env) => crate::utils::network::probe_rpc(&env.rpc).await,
+        None => IsOnline::Unknown,
+    };
+
+    *state.online_status.lock().unwrap() = status;
+    Ok(status)
+}
+
+#[command]
+pub fn get_online_status(state: TauriState<'_, AppState>) -> Result<IsOnline, String> {
+    Ok(*state.online_status.lock().unwrap())
+}
+
+/// Drops every cached entry whose key starts with `prefix` (e.g. `"sui:client"`
+/// or `"walrus"`), so the frontend can force a refresh after an action this
+/// module doesn't already invalidate for.
+#[command]
+pub fn invalidate_cache(prefix: String, state: TauriState<'_, AppState>) -> Result<(), String> {
+    crate::utils::cache::invalidate_prefix(&state, &prefix);
+    Ok(())
+}
+
+/// Changes how long `session_cache` entries stay fresh.
+#[command]
+pub fn set_cache_ttl(ttl_ms: u64, state: TauriState<'_, AppState>) -> Result<(), String> {
+    *state.cache_ttl_ms.lock().unwrap() = ttl_ms;
+    Ok(())
 }
@@ -0,0 +1,4 @@
+pub mod audit;
+pub mod cli;
+pub mod pipeline;
+pub mod walrus;
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+use std::process::Command as StdCommand;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tauri::command;
+use tauri::AppHandle;
+use tauri::State as TauriState;
+
+use crate::{AppState, CommandOutput};
+
+/// One command in a pipeline. `args` may contain `${step[<name>].<path>}`,
+/// `${step[<name>]}`, or `${env.NAME}` placeholders, resolved against prior
+/// steps' captured output before the child is spawned. `name` is how later
+/// steps refer to this step's output; it defaults to the step's index.
+#[derive(Deserialize)]
+pub struct PipelineStep {
+    program: String,
+    args: Vec<String>,
+    name: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct PipelineResult {
+    outputs: Vec<CommandOutput>,
+    variables: HashMap<String, Value>,
+}
+
+/// Runs `steps` sequentially, substituting `${step[i].field}` /
+/// `${env.NAME}` placeholders in each step's argv from prior steps' raw
+/// stdout before spawning the child (tokens are expanded directly into the
+/// `args` vector, never through a shell, so there's no injection risk). A
+/// step's stdout is captured as parsed JSON when possible, otherwise as the
+/// trimmed raw string, so plain-text output like an address still resolves.
+/// Stops at the first nonzero exit and returns everything run so far.
+#[command]
+pub async fn run_pipeline(
+    steps: Vec<PipelineStep>,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<PipelineResult, String> {
+    let mut outputs = Vec::new();
+    let mut variables: HashMap<String, Value> = HashMap::new();
+
+    for (index, step) in steps.iter().enumerate() {
+        let resolved_args = substitute_args(&step.args, &variables)?;
+        let (raw_stdout, output) = run_step(&step.program, &resolved_args)?;
+        let _ = crate::commands::audit::record(&app, &state, &step.program, &resolved_args, &output);
+
+        let key = step.name.clone().unwrap_or_else(|| index.to_string());
+        let raw_stdout = raw_stdout.trim();
+        if output.exit_code == 0 && !raw_stdout.is_empty() {
+            // Prefer parsed JSON so `${step[i].field}` can index into it;
+            // fall back to the raw string itself (e.g. the bare address
+            // `get_active_address` prints) so `${step[i]}` still resolves.
+            let captured = serde_json::from_str::<Value>(raw_stdout)
+                .unwrap_or_else(|_| Value::String(raw_stdout.to_string()));
+            variables.insert(key, captured);
+        }
+
+        let failed = output.exit_code != 0;
+        outputs.push(output);
+        if failed {
+            break;
+        }
+    }
+
+    Ok(PipelineResult { outputs, variables })
+}
+
+/// Runs one step and returns both its raw stdout (for variable capture,
+/// since substituting a later step's argv with a sanitized/truncated address
+/// or object id would make it unresolvable) and the sanitized `CommandOutput`
+/// meant for display.
+fn run_step(program: &str, args: &[String]) -> Result<(String, CommandOutput), String> {
+    let start = Instant::now();
+
+    let output = StdCommand::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute step: {}", e))?;
+
+    let duration_ms = start.elapsed().as_millis() as u64;
+    let raw_stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stdout = crate::utils::sanitizer::sanitize_output(&raw_stdout);
+    let stderr = crate::utils::sanitizer::sanitize_output(&String::from_utf8_lossy(&output.stderr));
+
+    let command_output = CommandOutput {
+        stdout,
+        stderr,
+        exit_code: output.status.code().unwrap_or(-1),
+        duration_ms,
+    };
+
+    Ok((raw_stdout, command_output))
+}
+
+fn substitute_args(args: &[String], variables: &HashMap<String, Value>) -> Result<Vec<String>, String> {
+    let placeholder = regex::Regex::new(r"\$\{([^}]+)\}").unwrap();
+
+    args.iter()
+        .map(|arg| {
+            let mut resolve_err = None;
+
+            let substituted = placeholder
+                .replace_all(arg, |caps: &regex::Captures| match resolve_token(&caps[1], variables) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        resolve_err = Some(e);
+                        String::new()
+                    }
+                })
+                .to_string();
+
+            match resolve_err {
+                Some(e) => Err(e),
+                None => Ok(substituted),
+            }
+        })
+        .collect()
+}
+
+fn resolve_token(token: &str, variables: &HashMap<String, Value>) -> Result<String, String> {
+    if let Some(name) = token.strip_prefix("env.") {
+        return std::env::var(name).map_err(|_| format!("Unknown env var: {}", name));
+    }
+
+    // The `.path` suffix is optional so a step whose captured output is a
+    // bare scalar (e.g. `get_active_address`'s `0x...` string, which isn't
+    // JSON and so has no fields to index into) can still be referenced as
+    // `${step[name]}`.
+    let step_ref = regex::Regex::new(r"^step\[(.+?)\](?:\.(.+))?$").unwrap();
+    let caps = step_ref
+        .captures(token)
+        .ok_or_else(|| format!("Invalid placeholder: ${{{}}}", token))?;
+    let step_key = &caps[1];
+    let path = caps.get(2).map(|m| m.as_str());
+
+    let mut current = variables
+        .get(step_key)
+        .ok_or_else(|| format!("No captured output for step '{}'", step_key))?;
+
+    if let Some(path) = path {
+        for part in path.split('.') {
+            current = current
+                .get(part)
+                .ok_or_else(|| format!("Field '{}' not found in step '{}' output", part, step_key))?;
+        }
+    }
+
+    Ok(match current {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
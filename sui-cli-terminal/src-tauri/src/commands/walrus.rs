@@ -1,21 +1,56 @@
 use tauri::command;
 use std::process::Command as StdCommand;
-use crate::CommandOutput;
+use serde::{Deserialize, Serialize};
+use crate::{AppState, CommandOutput};
+use tauri::AppHandle;
+use tauri::Emitter;
+use tauri::State as TauriState;
 
-#[command]
-pub async fn upload_blob(
-    path: String,
-    epochs: Option<u32>,
-) -> Result<CommandOutput, String> {
-    let mut cmd = StdCommand::new("walrus");
-    cmd.arg("store").arg(&path);
-    
-    if let Some(e) = epochs {
-        cmd.arg("--epochs").arg(e.to_string());
+#[derive(Clone, Serialize)]
+struct CacheHitEvent {
+    key: String,
+}
+
+/// A single `walrus list --json` entry.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct BlobInfo {
+    pub blob_id: String,
+    pub size: Option<u64>,
+    pub expiry_epoch: Option<u64>,
+    pub status: Option<String>,
+}
+
+/// Parses `walrus list` output into typed blobs. Prefers the `--json` path
+/// (a direct array of `BlobInfo`); falls back to a line scan for CLI
+/// versions without `--json` support, where only the blob id can be
+/// recovered.
+fn parse_blobs(output: &str) -> Vec<BlobInfo> {
+    if let Ok(blobs) = serde_json::from_str::<Vec<BlobInfo>>(output) {
+        return blobs;
     }
 
-    let output = cmd.output()
-        .map_err(|e| format!("Failed to upload blob: {}", e))?;
+    output
+        .lines()
+        .filter_map(|line| {
+            line.split_whitespace().next().map(|blob_id| BlobInfo {
+                blob_id: blob_id.to_string(),
+                size: None,
+                expiry_epoch: None,
+                status: None,
+            })
+        })
+        .collect()
+}
+
+/// Runs a single `walrus` invocation and returns its output. Shared by
+/// `upload_blob`, `download_blob` and `list_blobs`, which hit an
+/// RPC/storage-node endpoint and retry it on transient network failures
+/// (`upload_blob_streaming` runs `walrus` through `run_streaming` instead).
+async fn run_walrus_once(args: &[String]) -> Result<CommandOutput, String> {
+    let output = StdCommand::new("walrus")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to execute: {}", e))?;
 
     Ok(CommandOutput {
         stdout: String::from_utf8_lossy(&output.stdout).to_string(),
@@ -25,40 +60,96 @@ pub async fn upload_blob(
     })
 }
 
+#[command]
+pub async fn upload_blob(
+    path: String,
+    epochs: Option<u32>,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<CommandOutput, String> {
+    let mut args = vec!["store".to_string(), path];
+
+    if let Some(e) = epochs {
+        args.push("--epochs".to_string());
+        args.push(e.to_string());
+    }
+
+    let id = args.join(" ");
+    let online = *state.online_status.lock().unwrap();
+    let result = crate::utils::retry::run_with_retry(&id, &app, &online, || run_walrus_once(&args)).await?;
+    let _ = crate::commands::audit::record(&app, &state, "walrus", &args, &result);
+    crate::utils::cache::invalidate_prefix(&state, "walrus");
+    Ok(result)
+}
+
+/// Streaming counterpart to `upload_blob`: a `walrus store` of a large blob can
+/// take a while, so this emits a `command-output` event per sanitized line as
+/// it arrives instead of freezing the UI until the upload finishes. `id` ties
+/// the events together and lets `cancel_command` abort the upload.
+#[command]
+pub async fn upload_blob_streaming(
+    id: String,
+    path: String,
+    epochs: Option<u32>,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<CommandOutput, String> {
+    let mut args = vec!["store".to_string(), path];
+
+    if let Some(e) = epochs {
+        args.push("--epochs".to_string());
+        args.push(e.to_string());
+    }
+
+    let result = crate::utils::streaming::run_streaming("walrus", &args, id, &app, &state).await?;
+    let _ = crate::commands::audit::record(&app, &state, "walrus", &args, &result);
+    crate::utils::cache::invalidate_prefix(&state, "walrus");
+    Ok(result)
+}
+
 #[command]
 pub async fn download_blob(
     blob_id: String,
     output_path: Option<String>,
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
 ) -> Result<CommandOutput, String> {
-    let mut cmd = StdCommand::new("walrus");
-    cmd.arg("read").arg(&blob_id);
-    
+    let mut args = vec!["read".to_string(), blob_id];
+
     if let Some(path) = output_path {
-        cmd.arg("--out").arg(path);
+        args.push("--out".to_string());
+        args.push(path);
     }
 
-    let output = cmd.output()
-        .map_err(|e| format!("Failed to download blob: {}", e))?;
-
-    Ok(CommandOutput {
-        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-        exit_code: output.status.code().unwrap_or(-1),
-        duration_ms: 0,
-    })
+    let id = args.join(" ");
+    let online = *state.online_status.lock().unwrap();
+    let result = crate::utils::retry::run_with_retry(&id, &app, &online, || run_walrus_once(&args)).await?;
+    let _ = crate::commands::audit::record(&app, &state, "walrus", &args, &result);
+    Ok(result)
 }
 
 #[command]
-pub async fn list_blobs() -> Result<CommandOutput, String> {
-    let output = StdCommand::new("walrus")
-        .arg("list")
-        .output()
-        .map_err(|e| format!("Failed to list blobs: {}", e))?;
+pub async fn list_blobs(
+    app: AppHandle,
+    state: TauriState<'_, AppState>,
+) -> Result<Vec<BlobInfo>, String> {
+    let args = vec!["list".to_string(), "--json".to_string()];
+    let cache_key = crate::utils::cache::key("walrus", &args);
 
-    Ok(CommandOutput {
-        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
-        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
-        exit_code: output.status.code().unwrap_or(-1),
-        duration_ms: 0,
-    })
+    if let Some(cached) = crate::utils::cache::get::<Vec<BlobInfo>>(&state, &cache_key) {
+        let _ = app.emit("cache-hit", CacheHitEvent { key: cache_key });
+        return Ok(cached);
+    }
+
+    let id = args.join(" ");
+    let online = *state.online_status.lock().unwrap();
+    let result = crate::utils::retry::run_with_retry(&id, &app, &online, || run_walrus_once(&args)).await?;
+    let blobs = parse_blobs(&result.stdout);
+    let _ = crate::commands::audit::record(&app, &state, "walrus", &args, &result);
+
+    if result.exit_code == 0 {
+        crate::utils::cache::put(&state, &cache_key, &blobs);
+    }
+
+    Ok(blobs)
 }
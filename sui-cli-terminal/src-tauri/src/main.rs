@@ -7,7 +7,8 @@ mod utils;
 use tauri::{
     Manager,
 };
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use serde::{Serialize, Deserialize};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -25,9 +26,35 @@ pub struct CliError {
     message: String,
 }
 
+/// Last-known reachability of the active environment's RPC endpoint, kept in
+/// `AppState` and refreshed by `refresh_online_status` so the frontend can
+/// gate network actions and the retry loop can short-circuit when offline.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum IsOnline {
+    Online,
+    Offline,
+    Unknown,
+}
+
+/// Default time-to-live for `session_cache` entries; tunable at runtime via
+/// `set_cache_ttl`.
+const DEFAULT_CACHE_TTL_MS: u64 = 15_000;
+
 pub struct AppState {
     last_command: Mutex<String>,
-    session_cache: Mutex<std::collections::HashMap<String, CommandOutput>>,
+    /// Memoized results for idempotent read commands, keyed by normalized
+    /// argv. See `utils::cache`.
+    session_cache: Mutex<HashMap<String, utils::cache::CacheEntry>>,
+    cache_ttl_ms: Mutex<u64>,
+    /// Child processes spawned by a streaming command, keyed by the id the
+    /// frontend passed in, so `cancel_command` can find and kill them.
+    running_children: Mutex<HashMap<String, Arc<tokio::sync::Mutex<tokio::process::Child>>>>,
+    online_status: Mutex<IsOnline>,
+    /// Last-known active address/environment, refreshed as a side effect of
+    /// `get_active_address`/`refresh_online_status`, and read by the audit
+    /// log so every record carries context without an extra `sui` call.
+    current_address: Mutex<Option<String>>,
+    current_environment: Mutex<Option<String>>,
 }
 
 use window_vibrancy::{apply_vibrancy, NSVisualEffectMaterial};
@@ -35,7 +62,12 @@ use window_vibrancy::{apply_vibrancy, NSVisualEffectMaterial};
 fn main() {
     let app_state = AppState {
         last_command: Mutex::new(String::new()),
-        session_cache: Mutex::new(std::collections::HashMap::new()),
+        session_cache: Mutex::new(HashMap::new()),
+        cache_ttl_ms: Mutex::new(DEFAULT_CACHE_TTL_MS),
+        running_children: Mutex::new(HashMap::new()),
+        online_status: Mutex::new(IsOnline::Unknown),
+        current_address: Mutex::new(None),
+        current_environment: Mutex::new(None),
     };
 
     tauri::Builder::default()
@@ -43,18 +75,34 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             // CLI commands
             commands::cli::execute_sui_command,
+            commands::cli::execute_sui_command_streaming,
+            commands::cli::cancel_command,
             commands::cli::list_keys,
             commands::cli::generate_key,
             commands::cli::set_active_key,
-            
+
             // Walrus commands
             commands::walrus::upload_blob,
+            commands::walrus::upload_blob_streaming,
             commands::walrus::download_blob,
             commands::walrus::list_blobs,
-            
+
             // System commands
             commands::cli::get_active_address,
             commands::cli::get_environment,
+            commands::cli::refresh_online_status,
+            commands::cli::get_online_status,
+
+            // Pipeline commands
+            commands::pipeline::run_pipeline,
+
+            // Audit log commands
+            commands::audit::get_audit_log,
+            commands::audit::clear_audit_log,
+
+            // Cache commands
+            commands::cli::invalidate_cache,
+            commands::cli::set_cache_ttl,
         ])
         .setup(|app| {
             #[cfg(target_os = "macos")]
@@ -0,0 +1,66 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::AppState;
+
+/// A cached command result plus when it was inserted, so `get` can expire it
+/// once it's older than `AppState::cache_ttl_ms`.
+#[derive(Clone)]
+pub struct CacheEntry {
+    pub value: Value,
+    pub inserted_at_ms: u64,
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Builds a normalized cache key from a command's argv.
+pub fn key(program: &str, args: &[String]) -> String {
+    format!("{}:{}", program, args.join(" "))
+}
+
+/// Returns the cached value for `key`, deserialized as `T`, if present and
+/// younger than the configured TTL.
+pub fn get<T: DeserializeOwned>(state: &AppState, key: &str) -> Option<T> {
+    let ttl_ms = *state.cache_ttl_ms.lock().unwrap();
+    let cache = state.session_cache.lock().unwrap();
+    let entry = cache.get(key)?;
+
+    if now_ms().saturating_sub(entry.inserted_at_ms) > ttl_ms {
+        return None;
+    }
+
+    serde_json::from_value(entry.value.clone()).ok()
+}
+
+/// Stores `value` under `key` with the current timestamp, overwriting any
+/// previous entry.
+pub fn put<T: Serialize>(state: &AppState, key: &str, value: &T) {
+    if let Ok(serialized) = serde_json::to_value(value) {
+        state.session_cache.lock().unwrap().insert(
+            key.to_string(),
+            CacheEntry {
+                value: serialized,
+                inserted_at_ms: now_ms(),
+            },
+        );
+    }
+}
+
+/// Removes every cached entry whose key starts with `prefix`. Called after
+/// mutating operations (`set_active_key`, `generate_key`, `upload_blob`) so
+/// stale address/object lists don't linger.
+pub fn invalidate_prefix(state: &AppState, prefix: &str) {
+    state
+        .session_cache
+        .lock()
+        .unwrap()
+        .retain(|k, _| !k.starts_with(prefix));
+}
@@ -0,0 +1,6 @@
+pub mod cache;
+pub mod network;
+pub mod parser;
+pub mod retry;
+pub mod sanitizer;
+pub mod streaming;
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::IsOnline;
+
+/// Probes reachability of an RPC endpoint with a short TCP connect attempt.
+/// Used to keep `AppState::online_status` up to date so the frontend can gate
+/// actions and the retry loop can short-circuit when known-offline.
+pub async fn probe_rpc(rpc_url: &str) -> IsOnline {
+    let host_port = match extract_host_port(rpc_url) {
+        Some(hp) => hp,
+        None => return IsOnline::Unknown,
+    };
+
+    match timeout(Duration::from_secs(3), TcpStream::connect(&host_port)).await {
+        Ok(Ok(_)) => IsOnline::Online,
+        _ => IsOnline::Offline,
+    }
+}
+
+/// Pulls a `host:port` out of an RPC URL, defaulting the port from the scheme
+/// when the URL doesn't specify one.
+fn extract_host_port(url: &str) -> Option<String> {
+    let without_scheme = url.split("://").next_back()?;
+    let host_part = without_scheme.split('/').next()?;
+
+    if host_part.contains(':') {
+        Some(host_part.to_string())
+    } else if url.starts_with("https") {
+        Some(format!("{}:443", host_part))
+    } else {
+        Some(format!("{}:80", host_part))
+    }
+}
@@ -1,27 +1,87 @@
-use serde_json::Value;
-
-pub fn parse_keys(output: &str) -> Result<Vec<Value>, String> {
-    // Basic parsing logic - assumes output is somewhat structured or we parse line by line
-    // For now, implementing a simple parser that tries to extract key info
-    // In a real scenario, we might want to parse JSON output if CLI supports --json
-    
-    // If output is JSON, parse it directly
-    if let Ok(json) = serde_json::from_str::<Value>(output) {
-        if let Some(arr) = json.as_array() {
-            return Ok(arr.clone());
-        }
+use serde::{Deserialize, Serialize};
+
+/// A single `sui keytool list --json` entry.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct KeyInfo {
+    pub alias: Option<String>,
+    pub address: String,
+    pub scheme: Option<String>,
+    pub public_key: Option<String>,
+}
+
+/// A single `sui client envs --json` entry.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EnvInfo {
+    pub alias: String,
+    pub rpc: String,
+    pub active: bool,
+}
+
+/// Parses `sui keytool list` output into typed keys. Prefers the `--json`
+/// path (a direct array of `KeyInfo`); falls back to a line scan for CLI
+/// versions without `--json` support, where only the address can be
+/// recovered.
+pub fn parse_keys(output: &str) -> Result<Vec<KeyInfo>, String> {
+    if let Ok(keys) = serde_json::from_str::<Vec<KeyInfo>>(output) {
+        return Ok(keys);
     }
 
-    // Fallback: Parse text output
-    // This is a placeholder. Real implementation depends on `sui keytool list` output format.
+    // Fallback: Parse text output line by line, recovering just the address.
     let mut keys = Vec::new();
     for line in output.lines() {
-        if line.contains("0x") {
-            keys.push(serde_json::json!({
-                "raw": line
-            }));
+        if let Some(address) = line.split_whitespace().find(|tok| tok.starts_with("0x")) {
+            keys.push(KeyInfo {
+                alias: None,
+                address: address.to_string(),
+                scheme: None,
+                public_key: None,
+            });
         }
     }
-    
+
     Ok(keys)
 }
+
+/// Raw `sui client envs --json` entry, before we know which one is active.
+#[derive(Deserialize)]
+struct RawEnvEntry {
+    alias: String,
+    rpc: String,
+}
+
+/// Parses `sui client envs` output into typed environments. Prefers the
+/// `--json` path: `sui client envs --json` emits a tuple of `(envs,
+/// active_alias)` rather than a flat array, so that's what's decoded first,
+/// with `active` derived by matching each entry's alias against
+/// `active_alias`. Falls back to scanning the text table for CLI versions
+/// without `--json` support.
+pub fn parse_envs(output: &str) -> Result<Vec<EnvInfo>, String> {
+    if let Ok((entries, active_alias)) = serde_json::from_str::<(Vec<RawEnvEntry>, String)>(output) {
+        return Ok(entries
+            .into_iter()
+            .map(|e| EnvInfo {
+                active: e.alias == active_alias,
+                alias: e.alias,
+                rpc: e.rpc,
+            })
+            .collect());
+    }
+
+    let mut envs = Vec::new();
+    for line in output.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let rpc = tokens
+            .iter()
+            .find(|tok| tok.starts_with("http://") || tok.starts_with("https://"));
+
+        if let Some(rpc) = rpc {
+            envs.push(EnvInfo {
+                alias: tokens.first().copied().unwrap_or("").trim_matches('*').to_string(),
+                rpc: rpc.to_string(),
+                active: line.contains('*'),
+            });
+        }
+    }
+
+    Ok(envs)
+}
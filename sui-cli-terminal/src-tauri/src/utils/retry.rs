@@ -0,0 +1,115 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::{CommandOutput, IsOnline};
+
+const BASE_DELAY_MS: u64 = 500;
+const MAX_DELAY_MS: u64 = 16_000;
+const MAX_ATTEMPTS: u32 = 5;
+
+#[derive(Clone, Serialize)]
+struct RetryAttemptEvent {
+    id: String,
+    attempt: u32,
+    max_attempts: u32,
+    delay_ms: u64,
+    reason: String,
+}
+
+/// True if a failure looks transient (a nonzero exit whose stderr mentions a
+/// connection/timeout/503 issue) and is therefore worth retrying rather than
+/// surfacing immediately.
+pub fn is_retryable(exit_code: i32, stderr: &str) -> bool {
+    if exit_code == 0 {
+        return false;
+    }
+    let lower = stderr.to_lowercase();
+    [
+        "connection refused",
+        "connection reset",
+        "timed out",
+        "timeout",
+        "temporarily unavailable",
+        "503",
+        "could not connect",
+        "network is unreachable",
+    ]
+    .iter()
+    .any(|needle| lower.contains(needle))
+}
+
+/// Exponential backoff with jitter: a step of base 500ms doubling up to a 16s
+/// cap, of which the returned delay is half plus a random jitter of up to a
+/// quarter (250-375ms for the first retry, never more than 12s).
+fn backoff_delay(attempt: u32) -> Duration {
+    let exp = BASE_DELAY_MS.saturating_mul(1u64 << attempt.min(5));
+    let capped = exp.min(MAX_DELAY_MS);
+
+    let mut hasher = DefaultHasher::new();
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .hash(&mut hasher);
+    attempt.hash(&mut hasher);
+    let jitter = hasher.finish() % (capped / 4 + 1);
+
+    Duration::from_millis(capped / 2 + jitter)
+}
+
+/// Re-runs `attempt` with exponential backoff (see `backoff_delay`, up to
+/// `MAX_ATTEMPTS` tries) whenever it spawns/IO-errors or returns a
+/// retryable nonzero exit. Emits a `retry-attempt` event before each retry so
+/// the UI can show "retrying (2/5)". Short-circuits immediately once `online`
+/// is known `Offline`, instead of burning attempts against a dead network.
+pub async fn run_with_retry<F, Fut>(
+    id: &str,
+    app: &AppHandle,
+    online: &IsOnline,
+    attempt: F,
+) -> Result<CommandOutput, String>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<CommandOutput, String>>,
+{
+    if *online == IsOnline::Offline {
+        return Err("Aborting retries: network is offline".to_string());
+    }
+
+    for n in 0..MAX_ATTEMPTS {
+        let result = attempt().await;
+        let is_last = n + 1 == MAX_ATTEMPTS;
+
+        let should_retry = match &result {
+            Ok(output) => is_retryable(output.exit_code, &output.stderr),
+            Err(_) => true,
+        };
+
+        if !should_retry || is_last {
+            return result;
+        }
+
+        let reason = match &result {
+            Ok(output) => output.stderr.clone(),
+            Err(e) => e.clone(),
+        };
+        let delay = backoff_delay(n);
+        let _ = app.emit(
+            "retry-attempt",
+            RetryAttemptEvent {
+                id: id.to_string(),
+                attempt: n + 1,
+                max_attempts: MAX_ATTEMPTS,
+                delay_ms: delay.as_millis() as u64,
+                reason,
+            },
+        );
+        tokio::time::sleep(delay).await;
+    }
+
+    unreachable!("loop always returns by the last attempt")
+}
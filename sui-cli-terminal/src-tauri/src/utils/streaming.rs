@@ -0,0 +1,155 @@
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State as TauriState};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
+use tokio::sync::Mutex as AsyncMutex;
+
+use crate::{AppState, CommandOutput};
+
+#[derive(Clone, Serialize)]
+struct CommandOutputEvent {
+    id: String,
+    stream: &'static str,
+    line: String,
+    seq: u64,
+}
+
+#[derive(Clone, Serialize)]
+struct CommandCompleteEvent {
+    id: String,
+    output: CommandOutput,
+}
+
+/// Spawns `program` with `args`, streaming each sanitized stdout/stderr line to the
+/// frontend as a `command-output` event as soon as it arrives, instead of buffering
+/// the whole run like `StdCommand::output()` does. The child is registered under `id`
+/// in `AppState::running_children` for the duration of the run so `cancel_command`
+/// can kill it. Emits a terminal `command-output-complete` event carrying the final
+/// `CommandOutput` before returning it.
+pub async fn run_streaming(
+    program: &str,
+    args: &[String],
+    id: String,
+    app: &AppHandle,
+    state: &TauriState<'_, AppState>,
+) -> Result<CommandOutput, String> {
+    let start = Instant::now();
+
+    let mut child = TokioCommand::new(program)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to execute: {}", e))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "Failed to capture stdout".to_string())?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| "Failed to capture stderr".to_string())?;
+
+    let child = Arc::new(AsyncMutex::new(child));
+    state
+        .running_children
+        .lock()
+        .unwrap()
+        .insert(id.clone(), child.clone());
+
+    let mut stdout_lines = BufReader::new(stdout).lines();
+    let mut stderr_lines = BufReader::new(stderr).lines();
+
+    let mut full_stdout = String::new();
+    let mut full_stderr = String::new();
+    let mut seq: u64 = 0;
+    let mut stdout_done = false;
+    let mut stderr_done = false;
+
+    while !stdout_done || !stderr_done {
+        tokio::select! {
+            line = stdout_lines.next_line(), if !stdout_done => {
+                match line {
+                    Ok(Some(raw)) => {
+                        let sanitized = crate::utils::sanitizer::sanitize_output(&raw);
+                        full_stdout.push_str(&sanitized);
+                        full_stdout.push('\n');
+                        seq += 1;
+                        let _ = app.emit("command-output", CommandOutputEvent {
+                            id: id.clone(),
+                            stream: "stdout",
+                            line: sanitized,
+                            seq,
+                        });
+                    }
+                    _ => stdout_done = true,
+                }
+            }
+            line = stderr_lines.next_line(), if !stderr_done => {
+                match line {
+                    Ok(Some(raw)) => {
+                        let sanitized = crate::utils::sanitizer::sanitize_output(&raw);
+                        full_stderr.push_str(&sanitized);
+                        full_stderr.push('\n');
+                        seq += 1;
+                        let _ = app.emit("command-output", CommandOutputEvent {
+                            id: id.clone(),
+                            stream: "stderr",
+                            line: sanitized,
+                            seq,
+                        });
+                    }
+                    _ => stderr_done = true,
+                }
+            }
+        }
+    }
+
+    let status = child
+        .lock()
+        .await
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait on child: {}", e))?;
+    state.running_children.lock().unwrap().remove(&id);
+
+    let output = CommandOutput {
+        stdout: full_stdout,
+        stderr: full_stderr,
+        exit_code: status.code().unwrap_or(-1),
+        duration_ms: start.elapsed().as_millis() as u64,
+    };
+
+    let _ = app.emit(
+        "command-output-complete",
+        CommandCompleteEvent {
+            id,
+            output: output.clone(),
+        },
+    );
+
+    Ok(output)
+}
+
+/// Kills the tracked child process for `id`, if one is still running.
+pub fn cancel(id: &str, state: &TauriState<'_, AppState>) -> Result<(), String> {
+    let child = {
+        let children = state.running_children.lock().unwrap();
+        children.get(id).cloned()
+    };
+
+    match child {
+        Some(child) => {
+            tauri::async_runtime::spawn(async move {
+                let _ = child.lock().await.start_kill();
+            });
+            Ok(())
+        }
+        None => Err(format!("No running command with id {}", id)),
+    }
+}